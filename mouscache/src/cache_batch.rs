@@ -0,0 +1,14 @@
+use crate::Result;
+use crate::Cacheable;
+
+/// Batch counterpart to `CacheAccess` for bulk loads and invalidations: one
+/// pooled connection and a single `redis::pipe()` round-trip for `RedisCache`,
+/// one object-cache lock held across the whole loop for `MemoryCache`,
+/// instead of paying a per-key round-trip/lock for every item.
+/// `ClusterRedisCache` reuses one connection for the batch but still sends
+/// one command per key, since the keys can span shards.
+pub trait CacheBatch {
+    fn insert_many<K: ToString, O: Cacheable + Clone + 'static>(&self, items: &[(K, O)]) -> Result<()>;
+    fn get_many<K: ToString, O: Cacheable + Clone + 'static>(&self, keys: &[K]) -> Result<Vec<Option<O>>>;
+    fn remove_many<K: ToString, O: Cacheable>(&self, keys: &[K]) -> Result<()>;
+}
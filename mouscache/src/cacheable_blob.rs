@@ -0,0 +1,22 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Implemented instead of `Cacheable` by types that should be stored as a
+/// single JSON-encoded value (`SET`/`GET` in `RedisCache`, a single string in
+/// `MemoryCache`) rather than broken up into hash fields. Useful for nested
+/// structs, enums and collections that `Cacheable::to_redis_obj`'s flat
+/// `Vec<(String, String)>` can't represent.
+pub trait CacheableBlob: Serialize + DeserializeOwned {
+    fn model_name() -> &'static str where Self: Sized;
+
+    fn expires_after(&self) -> Option<usize> {
+        None
+    }
+}
+
+pub(crate) fn blob_key_create<K: ToString, O: CacheableBlob>(key: K) -> String {
+    let mut blob_key = String::from(O::model_name());
+    blob_key.push_str(":");
+    blob_key.push_str(key.to_string().as_str());
+    blob_key
+}
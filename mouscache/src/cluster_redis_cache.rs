@@ -0,0 +1,481 @@
+use std::collections::hash_map::HashMap;
+use std::str::FromStr;
+use Cache;
+use Cache::ClusterRedis;
+use Result;
+use CacheError;
+use Cacheable;
+use CacheAccess;
+use CacheFunc;
+use CacheBatch;
+use redis;
+use redis::Commands;
+use redis::cluster::{ClusterClient, ClusterConnection};
+
+#[cfg(feature = "serde")]
+use cacheable_blob::{CacheableBlob, blob_key_create};
+#[cfg(feature = "serde")]
+use serde_json;
+
+#[allow(dead_code)]
+pub struct ClusterRedisCache {
+    client: ClusterClient,
+}
+
+impl ClusterRedisCache {
+    pub fn new(hosts: &[&str], password: Option<&str>) -> Result<Cache> {
+        let urls: Vec<String> = hosts.iter().map(|host| {
+            match password {
+                Some(p) => format!("redis://:{}@{}", p, host),
+                None => format!("redis://{}", host),
+            }
+        }).collect();
+
+        let client = match ClusterClient::open(urls) {
+            Ok(c) => c,
+            Err(e) => return Err(CacheError::Other(e.to_string())),
+        };
+
+        Ok(ClusterRedis(ClusterRedisCache {
+            client,
+        }))
+    }
+
+    fn connection(&self) -> Result<ClusterConnection> {
+        match self.client.get_connection() {
+            Ok(con) => Ok(con),
+            Err(e) => Err(CacheError::ConnectionError(e.to_string())),
+        }
+    }
+}
+
+/// A command can fail because the slot it targeted moved to another node
+/// (`MOVED`/`ASK`) or the cluster is mid-reconfiguration (`TRYAGAIN`/
+/// `CLUSTERDOWN`) — that's a connection-topology problem, not a bad
+/// request, so it's reported as `CacheError::ConnectionError` regardless
+/// of which command hit it. Anything else keeps the caller's usual
+/// classification via `fallback`.
+fn map_command_error(e: redis::RedisError, fallback: fn(String) -> CacheError) -> CacheError {
+    match e.kind() {
+        redis::ErrorKind::Moved | redis::ErrorKind::Ask
+        | redis::ErrorKind::TryAgain | redis::ErrorKind::ClusterDown => CacheError::ConnectionError(e.to_string()),
+        _ => fallback(e.to_string()),
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ClusterRedisCache {
+    /// Stores `obj` JSON-encoded under a single key via SET, for types that
+    /// opt into blob storage instead of `Cacheable`'s hash-field layout.
+    pub fn insert_blob<K: ToString, O: CacheableBlob>(&mut self, key: K, obj: &O) -> Result<()> {
+        let connection = self.connection()?;
+
+        let redis_key = blob_key_create::<K, O>(key);
+        let json = match serde_json::to_string(obj) {
+            Ok(j) => j,
+            Err(e) => return Err(CacheError::InsertionError(e.to_string())),
+        };
+
+        if let Some(ttl) = obj.expires_after() {
+            redis_set_with_expire(&connection, redis_key, json, ttl)
+        } else {
+            redis_set(&connection, redis_key, json)
+        }
+    }
+
+    pub fn get_blob<K: ToString, O: CacheableBlob>(&mut self, key: K) -> Result<Option<O>> {
+        let connection = self.connection()?;
+
+        match redis_get(&connection, blob_key_create::<K, O>(key)) {
+            Ok(Some(json)) => Ok(serde_json::from_str(json.as_str()).ok()),
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn remove_blob<K: ToString, O: CacheableBlob>(&mut self, key: K) -> Result<()> {
+        let connection = self.connection()?;
+
+        redis_delete(&connection, blob_key_create::<K, O>(key))
+    }
+}
+
+#[cfg(feature = "serde")]
+fn redis_set(con: &ClusterConnection, key: String, value: String) -> Result<()> {
+    match con.set::<String, String, ()>(key, value) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(map_command_error(e, CacheError::InsertionError)),
+    }
+}
+
+#[cfg(feature = "serde")]
+fn redis_set_with_expire(con: &ClusterConnection, key: String, value: String, ttl_sec: usize) -> Result<()> {
+    match con.set_ex::<String, String, ()>(key, value, ttl_sec) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(map_command_error(e, CacheError::InsertionError)),
+    }
+}
+
+#[cfg(feature = "serde")]
+fn redis_get(con: &ClusterConnection, key: String) -> Result<Option<String>> {
+    match con.get::<String, Option<String>>(key) {
+        Ok(v) => Ok(v),
+        Err(e) => Err(map_command_error(e, CacheError::Other)),
+    }
+}
+
+impl CacheAccess for ClusterRedisCache {
+    fn insert<K: ToString, O: Cacheable + 'static>(&mut self, key: K, obj: O) -> Result<()> {
+        let connection = self.connection()?;
+
+        let redis_key = redis_key_create::<K, O>(key);
+        let data = obj.to_redis_obj();
+        if let Some(ttl) = obj.expires_after() {
+            redis_hash_set_multiple_with_expire(&connection, redis_key, &data, ttl)
+        } else {
+            redis_hash_set_multiple(&connection, redis_key, &data)
+        }
+    }
+
+    fn get<K: ToString, O: Cacheable + 'static>(&mut self, key: K) -> Result<Option<O>> {
+        let connection = self.connection()?;
+
+        let redis_key = redis_key_create::<K, O>(key);
+        if let Ok(val) = redis_hash_get_all(&connection, redis_key) {
+            if let Ok(c) = O::from_redis_obj(val) {
+                Ok(Some(c))
+            } else {
+                Ok(None)
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn remove<K: ToString, O: Cacheable>(&mut self, key: K) -> Result<()> {
+        let connection = self.connection()?;
+
+        let redis_key = redis_key_create::<K, O>(key);
+        redis_delete(&connection, redis_key)
+    }
+}
+
+impl CacheFunc for ClusterRedisCache {
+    fn expire(&self, key: &str, ttl: usize) -> Result<bool> {
+        let connection = self.connection()?;
+
+        match connection.expire::<&str, bool>(key, ttl) {
+            Ok(was_set) => Ok(was_set),
+            Err(e) => Err(map_command_error(e, CacheError::Other)),
+        }
+    }
+
+    fn hash_delete(&self, key: &str, fields: &[&str]) -> Result<bool> {
+        let connection = self.connection()?;
+
+        match connection.hdel::<&str, &[&str], u64>(key, fields) {
+            Ok(_) => Ok(true),
+            Err(e) => Err(map_command_error(e, CacheError::DeletionError)),
+        }
+    }
+
+    fn hash_exists(&self, key: &str, field: &str) -> Result<bool> {
+        let connection = self.connection()?;
+
+        match connection.hexists::<&str, &str, bool>(key, field) {
+            Ok(exists) => Ok(exists),
+            Err(e) => Err(map_command_error(e, CacheError::Other)),
+        }
+    }
+
+    fn hash_get<T: FromStr>(&self, key: &str, field: &str) -> Result<Option<T>> {
+        let connection = self.connection()?;
+
+        match connection.hget::<&str, &str, Option<String>>(key, field) {
+            Ok(Some(val)) => T::from_str(val.as_str()).map(Some)
+                .map_err(|_| CacheError::Other("Unable to parse value into desired type".to_string())),
+            Ok(None) => Ok(None),
+            Err(e) => Err(map_command_error(e, CacheError::Other)),
+        }
+    }
+
+    fn hash_get_all<T: Cacheable + Clone + 'static>(&self, key: &str) -> Result<Option<T>> {
+        let connection = self.connection()?;
+
+        let redis_key = redis_key_create::<&str, T>(key);
+        if let Ok(val) = redis_hash_get_all(&connection, redis_key) {
+            if let Ok(c) = T::from_redis_obj(val) {
+                Ok(Some(c))
+            } else {
+                Ok(None)
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn hash_keys(&self, key: &str) -> Result<Vec<String>> {
+        let connection = self.connection()?;
+
+        match connection.hkeys::<&str, Vec<String>>(key) {
+            Ok(keys) => Ok(keys),
+            Err(e) => Err(map_command_error(e, CacheError::Other)),
+        }
+    }
+
+    fn hash_len(&self, key: &str) -> Result<usize> {
+        let connection = self.connection()?;
+
+        match connection.hlen::<&str, usize>(key) {
+            Ok(len) => Ok(len),
+            Err(e) => Err(map_command_error(e, CacheError::Other)),
+        }
+    }
+
+    fn hash_multiple_get(&self, key: &str, fields: &[&str]) -> Result<Vec<Option<String>>> {
+        let connection = self.connection()?;
+
+        match redis::cmd("HMGET").arg(key).arg(fields).query::<Vec<Option<String>>>(&connection) {
+            Ok(vals) => Ok(vals),
+            Err(e) => Err(map_command_error(e, CacheError::Other)),
+        }
+    }
+
+    fn hash_multiple_set<V: ToString>(&self, key: &str, fv_pairs: &[(&str, V)]) -> Result<bool> {
+        let connection = self.connection()?;
+
+        let data: Vec<(&str, String)> = fv_pairs.iter().map(|&(f, ref v)| (f, v.to_string())).collect();
+        match connection.hset_multiple::<&str, &str, String, ()>(key, &data) {
+            Ok(_) => Ok(true),
+            Err(e) => Err(map_command_error(e, CacheError::InsertionError)),
+        }
+    }
+
+    fn hash_set<V: ToString>(&self, key: &str, field: &str, value: V) -> Result<bool> {
+        let connection = self.connection()?;
+
+        match connection.hset::<&str, &str, String, ()>(key, field, value.to_string()) {
+            Ok(_) => Ok(true),
+            Err(e) => Err(map_command_error(e, CacheError::InsertionError)),
+        }
+    }
+
+    fn hash_set_all<T: Cacheable + Clone + 'static>(&self, key: &str, cacheable: T) -> Result<bool> {
+        let connection = self.connection()?;
+
+        let redis_key = redis_key_create::<&str, T>(key);
+        let data = cacheable.to_redis_obj();
+        if let Some(ttl) = cacheable.expires_after() {
+            redis_hash_set_multiple_with_expire(&connection, redis_key, &data, ttl).map(|_| true)
+        } else {
+            redis_hash_set_multiple(&connection, redis_key, &data).map(|_| true)
+        }
+    }
+
+    fn hash_set_if_not_exists<V: ToString>(&self, key: &str, field: &str, value: V) -> Result<bool> {
+        let connection = self.connection()?;
+
+        match connection.hset_nx::<&str, &str, String, bool>(key, field, value.to_string()) {
+            Ok(set) => Ok(set),
+            Err(e) => Err(map_command_error(e, CacheError::InsertionError)),
+        }
+    }
+
+    fn hash_values(&self, key: &str) -> Result<Vec<String>> {
+        let connection = self.connection()?;
+
+        match connection.hvals::<&str, Vec<String>>(key) {
+            Ok(vals) => Ok(vals),
+            Err(e) => Err(map_command_error(e, CacheError::Other)),
+        }
+    }
+
+    fn set_add<V: ToString>(&self, key: &str, members: &[V]) -> Result<bool> {
+        let connection = self.connection()?;
+
+        let data: Vec<String> = members.iter().map(|m| m.to_string()).collect();
+        match connection.sadd::<&str, &Vec<String>, u64>(key, &data) {
+            Ok(_) => Ok(true),
+            Err(e) => Err(map_command_error(e, CacheError::InsertionError)),
+        }
+    }
+
+    fn set_card(&self, key: &str) -> Result<u64> {
+        let connection = self.connection()?;
+
+        match connection.scard::<&str, u64>(key) {
+            Ok(card) => Ok(card),
+            Err(e) => Err(map_command_error(e, CacheError::Other)),
+        }
+    }
+
+    fn set_diff(&self, keys: &[&str]) -> Result<Vec<String>> {
+        let connection = self.connection()?;
+
+        match connection.sdiff::<&[&str], Vec<String>>(keys) {
+            Ok(res) => Ok(res),
+            Err(e) => Err(map_command_error(e, CacheError::Other)),
+        }
+    }
+
+    fn set_diffstore(&self, diff_name: &str, keys: &[&str]) -> Result<u64> {
+        let connection = self.connection()?;
+
+        match connection.sdiffstore::<&str, &[&str], u64>(diff_name, keys) {
+            Ok(n) => Ok(n),
+            Err(e) => Err(map_command_error(e, CacheError::InsertionError)),
+        }
+    }
+
+    fn set_inter(&self, keys: &[&str]) -> Result<Vec<String>> {
+        let connection = self.connection()?;
+
+        match connection.sinter::<&[&str], Vec<String>>(keys) {
+            Ok(res) => Ok(res),
+            Err(e) => Err(map_command_error(e, CacheError::Other)),
+        }
+    }
+
+    fn set_interstore(&self, inter_name: &str, keys: &[&str]) -> Result<u64> {
+        let connection = self.connection()?;
+
+        match connection.sinterstore::<&str, &[&str], u64>(inter_name, keys) {
+            Ok(n) => Ok(n),
+            Err(e) => Err(map_command_error(e, CacheError::InsertionError)),
+        }
+    }
+
+    fn set_ismember<V: ToString>(&self, key: &str, member: V) -> Result<bool> {
+        let connection = self.connection()?;
+
+        match connection.sismember::<&str, String, bool>(key, member.to_string()) {
+            Ok(is_member) => Ok(is_member),
+            Err(e) => Err(map_command_error(e, CacheError::Other)),
+        }
+    }
+
+    fn set_members(&self, key: &str) -> Result<Vec<String>> {
+        let connection = self.connection()?;
+
+        match connection.smembers::<&str, Vec<String>>(key) {
+            Ok(members) => Ok(members),
+            Err(e) => Err(map_command_error(e, CacheError::Other)),
+        }
+    }
+
+    fn set_move<V: ToString>(&self, key1: &str, key2: &str, member: V) -> Result<bool> {
+        let connection = self.connection()?;
+
+        match connection.smove::<&str, &str, String, bool>(key1, key2, member.to_string()) {
+            Ok(moved) => Ok(moved),
+            Err(e) => Err(map_command_error(e, CacheError::Other)),
+        }
+    }
+
+    fn set_rem<V: ToString>(&self, key: &str, member: V) -> Result<bool> {
+        let connection = self.connection()?;
+
+        match connection.srem::<&str, String, u64>(key, member.to_string()) {
+            Ok(n) => Ok(n > 0),
+            Err(e) => Err(map_command_error(e, CacheError::DeletionError)),
+        }
+    }
+
+    fn set_union(&self, keys: &[&str]) -> Result<Vec<String>> {
+        let connection = self.connection()?;
+
+        match connection.sunion::<&[&str], Vec<String>>(keys) {
+            Ok(res) => Ok(res),
+            Err(e) => Err(map_command_error(e, CacheError::Other)),
+        }
+    }
+
+    fn set_unionstore(&self, union_name: &str, keys: &[&str]) -> Result<u64> {
+        let connection = self.connection()?;
+
+        match connection.sunionstore::<&str, &[&str], u64>(union_name, keys) {
+            Ok(n) => Ok(n),
+            Err(e) => Err(map_command_error(e, CacheError::InsertionError)),
+        }
+    }
+}
+
+// Keys in a slice can land on different shards, so a single `redis::pipe()`
+// round-trip isn't guaranteed to stay within one node's hash slots. Each
+// item still gets its own command, as in `CacheAccess`, but all of them share
+// one cluster connection taken per batch call, and batching the call keeps
+// the ergonomics of `CacheBatch` consistent across backends.
+impl CacheBatch for ClusterRedisCache {
+    fn insert_many<K: ToString, O: Cacheable + Clone + 'static>(&self, items: &[(K, O)]) -> Result<()> {
+        let connection = self.connection()?;
+
+        for &(ref key, ref obj) in items {
+            let redis_key = redis_key_create::<String, O>(key.to_string());
+            let data = obj.to_redis_obj();
+            if let Some(ttl) = obj.expires_after() {
+                redis_hash_set_multiple_with_expire(&connection, redis_key, &data, ttl)?;
+            } else {
+                redis_hash_set_multiple(&connection, redis_key, &data)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn get_many<K: ToString, O: Cacheable + Clone + 'static>(&self, keys: &[K]) -> Result<Vec<Option<O>>> {
+        let connection = self.connection()?;
+
+        Ok(keys.iter().map(|key| {
+            let redis_key = redis_key_create::<String, O>(key.to_string());
+            match redis_hash_get_all(&connection, redis_key) {
+                Ok(val) => O::from_redis_obj(val).ok(),
+                Err(_) => None,
+            }
+        }).collect())
+    }
+
+    fn remove_many<K: ToString, O: Cacheable>(&self, keys: &[K]) -> Result<()> {
+        let connection = self.connection()?;
+
+        for key in keys {
+            redis_delete(&connection, redis_key_create::<String, O>(key.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+fn redis_key_create<K: ToString, O: Cacheable>(key: K) -> String {
+    let mut redis_key = String::from(O::model_name());
+    redis_key.push_str(":");
+    redis_key.push_str(key.to_string().as_str());
+    redis_key
+}
+
+fn redis_hash_set_multiple_with_expire<F: redis::ToRedisArgs, V: redis::ToRedisArgs>(con: &ClusterConnection, key: String, v: &[(F, V)], ttl_sec: usize) -> Result<()> {
+    redis_hash_set_multiple(con, key.clone(), v)?;
+
+    match con.expire(key, ttl_sec) {
+        Ok(v) => Ok(v),
+        Err(e) => Err(map_command_error(e, CacheError::InsertionError)),
+    }
+}
+
+fn redis_hash_set_multiple<F: redis::ToRedisArgs, V: redis::ToRedisArgs>(con: &ClusterConnection, key: String, v: &[(F, V)]) -> Result<()> {
+    match con.hset_multiple::<String, F, V, ()>(key, v) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(map_command_error(e, CacheError::InsertionError)),
+    }
+}
+
+fn redis_hash_get_all(con: &ClusterConnection, key: String) -> Result<HashMap<String, String>> {
+    match con.hgetall::<String, HashMap<String, String>>(key) {
+        Ok(v) => Ok(v),
+        Err(e) => Err(CacheError::ConnectionError(e.to_string())),
+    }
+}
+
+fn redis_delete(con: &ClusterConnection, key: String) -> Result<()> {
+    match con.del::<String, ()>(key) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(map_command_error(e, CacheError::DeletionError)),
+    }
+}
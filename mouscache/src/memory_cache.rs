@@ -5,9 +5,18 @@ use crate::Result;
 use crate::Cacheable;
 use crate::CacheAccess;
 use crate::CacheFunc;
+use crate::CacheBatch;
 use parking_lot::RwLock;
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::str::FromStr;
+use std::thread;
+use std::thread::JoinHandle;
+
+#[cfg(feature = "serde")]
+use crate::cacheable_blob::{CacheableBlob, blob_key_create};
+#[cfg(feature = "serde")]
+use serde_json;
 
 struct Expiration {
     insertion_time: Instant,
@@ -34,6 +43,15 @@ struct Inner {
     pub obj_cache: RwLock<HashMap<String, MemCacheable>>,
     pub hashsets: RwLock<HashMap<String, RwLock<HashMap<String, String>>>>,
     pub sets: RwLock<HashMap<String, RwLock<HashSet<String>>>>,
+    hash_expirations: RwLock<HashMap<String, Expiration>>,
+    set_expirations: RwLock<HashMap<String, Expiration>>,
+    #[cfg(feature = "serde")]
+    blob_cache: RwLock<HashMap<String, (String, Option<Expiration>)>>,
+    capacity: Option<usize>,
+    recency: RwLock<HashMap<String, u64>>,
+    recency_counter: AtomicU64,
+    reap_running: Arc<AtomicBool>,
+    reap_handle: RwLock<Option<JoinHandle<()>>>,
 }
 
 impl Inner {
@@ -42,6 +60,135 @@ impl Inner {
             obj_cache: RwLock::new(HashMap::new()),
             hashsets: RwLock::new(HashMap::new()),
             sets: RwLock::new(HashMap::new()),
+            hash_expirations: RwLock::new(HashMap::new()),
+            set_expirations: RwLock::new(HashMap::new()),
+            #[cfg(feature = "serde")]
+            blob_cache: RwLock::new(HashMap::new()),
+            capacity: None,
+            recency: RwLock::new(HashMap::new()),
+            recency_counter: AtomicU64::new(0),
+            reap_running: Arc::new(AtomicBool::new(true)),
+            reap_handle: RwLock::new(None),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut inner = Inner::new();
+        inner.capacity = Some(capacity);
+        inner
+    }
+
+    /// Removes object, hash and set entries whose TTL has elapsed. Only
+    /// called periodically by the reaper thread spawned by
+    /// `MemoryCache::with_reaper` or `MemoryCache::with_capacity_and_reaper`.
+    /// Without a reaper, `CacheAccess::get`
+    /// still lazily evicts an expired object on access, but expired hashes
+    /// and sets are never removed.
+    fn reap_expired(&self) {
+        let expired_objects: Vec<String> = self.obj_cache.read().iter()
+            .filter(|&(_, &(_, ref exp))| exp.as_ref().map_or(false, |e| e.is_expired()))
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        if !expired_objects.is_empty() {
+            let mut cache = self.obj_cache.write();
+            for key in &expired_objects {
+                cache.remove(key);
+            }
+        }
+        for key in &expired_objects {
+            self.forget(key);
+        }
+
+        let expired_hashes: Vec<String> = self.hash_expirations.read().iter()
+            .filter(|&(_, exp)| exp.is_expired())
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        if !expired_hashes.is_empty() {
+            let mut hashsets = self.hashsets.write();
+            let mut expirations = self.hash_expirations.write();
+            for key in &expired_hashes {
+                hashsets.remove(key);
+                expirations.remove(key);
+            }
+        }
+
+        let expired_sets: Vec<String> = self.set_expirations.read().iter()
+            .filter(|&(_, exp)| exp.is_expired())
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        if !expired_sets.is_empty() {
+            let mut sets = self.sets.write();
+            let mut expirations = self.set_expirations.write();
+            for key in &expired_sets {
+                sets.remove(key);
+                expirations.remove(key);
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        {
+            let expired_blobs: Vec<String> = self.blob_cache.read().iter()
+                .filter(|&(_, &(_, ref exp))| exp.as_ref().map_or(false, |e| e.is_expired()))
+                .map(|(key, _)| key.clone())
+                .collect();
+
+            if !expired_blobs.is_empty() {
+                let mut blobs = self.blob_cache.write();
+                for key in &expired_blobs {
+                    blobs.remove(key);
+                }
+            }
+        }
+    }
+
+    fn expire_hash(&self, key: &str, ttl: usize) -> bool {
+        if self.hash_exists(key) {
+            self.hash_expirations.write().insert(key.to_string(), Expiration::new(ttl));
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expire_set(&self, key: &str, ttl: usize) -> bool {
+        if self.set_exists(key) {
+            self.set_expirations.write().insert(key.to_string(), Expiration::new(ttl));
+            true
+        } else {
+            false
+        }
+    }
+
+    fn touch(&self, key: &str) {
+        let tick = self.recency_counter.fetch_add(1, Ordering::Relaxed);
+        self.recency.write().insert(key.to_string(), tick);
+    }
+
+    fn forget(&self, key: &str) {
+        self.recency.write().remove(key);
+    }
+
+    fn evict_lru_if_over_capacity(&self) {
+        let capacity = match self.capacity {
+            Some(capacity) => capacity,
+            None => return,
+        };
+
+        if self.obj_cache.read().len() <= capacity {
+            return;
+        }
+
+        let lru_key = self.recency.read()
+            .iter()
+            .min_by_key(|&(_, tick)| *tick)
+            .map(|(key, _)| key.clone());
+
+        if let Some(lru_key) = lru_key {
+            self.obj_cache.write().remove(&lru_key);
+            self.forget(&lru_key);
         }
     }
 
@@ -78,6 +225,12 @@ impl Inner {
     }
 }
 
+impl Drop for Inner {
+    fn drop(&mut self) {
+        self.reap_running.store(false, Ordering::SeqCst);
+    }
+}
+
 pub struct MemoryCache {
     inner: Arc<Inner>
 }
@@ -96,6 +249,101 @@ impl MemoryCache {
             inner: Arc::new(Inner::new())
         }
     }
+
+    /// Creates a `MemoryCache` that evicts the least recently used object once
+    /// `capacity` objects are held, instead of growing without bound.
+    pub fn with_capacity(capacity: usize) -> MemoryCache {
+        MemoryCache {
+            inner: Arc::new(Inner::with_capacity(capacity))
+        }
+    }
+
+    /// Creates a `MemoryCache` backed by a reaper thread that wakes up every
+    /// `interval` to drop expired objects, hashes and sets instead of relying
+    /// solely on lazy expiration on read. The thread stops once every clone of
+    /// the returned `MemoryCache` has been dropped. This cache is unbounded;
+    /// use `with_capacity_and_reaper` to also bound it by object count.
+    pub fn with_reaper(interval: Duration) -> MemoryCache {
+        Self::spawn_reaper(Arc::new(Inner::new()), interval)
+    }
+
+    /// Creates a `MemoryCache` that combines `with_capacity`'s LRU eviction
+    /// with `with_reaper`'s background expiration, since neither alone
+    /// covers both concerns: LRU eviction bounds object count but doesn't
+    /// touch hashes or sets, and the reaper catches TTL expiry on all three
+    /// stores but doesn't bound memory use.
+    pub fn with_capacity_and_reaper(capacity: usize, interval: Duration) -> MemoryCache {
+        Self::spawn_reaper(Arc::new(Inner::with_capacity(capacity)), interval)
+    }
+
+    fn spawn_reaper(inner: Arc<Inner>, interval: Duration) -> MemoryCache {
+        let weak_inner: Weak<Inner> = Arc::downgrade(&inner);
+        let running = inner.reap_running.clone();
+
+        let handle = thread::spawn(move || {
+            while running.load(Ordering::SeqCst) {
+                thread::sleep(interval);
+
+                if !running.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                match weak_inner.upgrade() {
+                    Some(inner) => inner.reap_expired(),
+                    None => break,
+                }
+            }
+        });
+
+        *inner.reap_handle.write() = Some(handle);
+
+        MemoryCache { inner }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl MemoryCache {
+    /// Stores `obj` JSON-encoded under a single key, for types that opt into
+    /// blob storage instead of `Cacheable`'s hash-field layout.
+    pub fn insert_blob<K: ToString, O: CacheableBlob>(&self, key: K, obj: &O) -> Result<()> {
+        let blob_key = blob_key_create::<K, O>(key);
+        let json = match serde_json::to_string(obj) {
+            Ok(j) => j,
+            Err(e) => return Err(crate::CacheError::InsertionError(e.to_string())),
+        };
+
+        let exp = obj.expires_after().map(|ttl| { Expiration::new(ttl) });
+        self.inner.blob_cache.write().insert(blob_key, (json, exp));
+        Ok(())
+    }
+
+    pub fn get_blob<K: ToString, O: CacheableBlob>(&self, key: K) -> Result<Option<O>> {
+        let blob_key = blob_key_create::<K, O>(key);
+
+        let mut delete_entry = false;
+
+        {
+            let cache = self.inner.blob_cache.read();
+            if let Some(&(ref json, ref exp)) = cache.get(&blob_key) {
+                if exp.as_ref().map_or(false, |e| e.is_expired()) {
+                    delete_entry = true;
+                } else {
+                    return Ok(serde_json::from_str(json.as_str()).ok());
+                }
+            }
+        }
+
+        if delete_entry {
+            self.inner.blob_cache.write().remove(&blob_key);
+        }
+
+        Ok(None)
+    }
+
+    pub fn remove_blob<K: ToString, O: CacheableBlob>(&self, key: K) -> Result<()> {
+        self.inner.blob_cache.write().remove(&blob_key_create::<K, O>(key));
+        Ok(())
+    }
 }
 
 impl CacheAccess for MemoryCache {
@@ -109,7 +357,9 @@ impl CacheAccess for MemoryCache {
 
         let exp = expires_after.map(|ttl| { Expiration::new(ttl) });
 
-        self.inner.obj_cache.write().insert(tkey, (Box::new(obj), exp));
+        self.inner.obj_cache.write().insert(tkey.clone(), (Box::new(obj), exp));
+        self.inner.touch(&tkey);
+        self.inner.evict_lru_if_over_capacity();
         Ok(())
     }
 
@@ -133,6 +383,7 @@ impl CacheAccess for MemoryCache {
                         None => panic!("Invalid type in mouscache")
                     };
 
+                    self.inner.touch(&tkey);
                     return Ok(Some(struct_obj));
                 }
             }
@@ -141,6 +392,7 @@ impl CacheAccess for MemoryCache {
         if delete_entry {
             let mut cache = self.inner.obj_cache.write();
             cache.remove(&tkey);
+            self.inner.forget(&tkey);
         }
 
         Ok(None)
@@ -155,6 +407,7 @@ impl CacheAccess for MemoryCache {
     fn remove<K: ToString, O: Cacheable>(&self, key: K) -> Result<()> {
         let tkey = gen_key::<K, O>(key);
         self.inner.obj_cache.write().remove(&tkey);
+        self.inner.forget(&tkey);
         Ok(())
     }
 }
@@ -163,7 +416,62 @@ fn gen_key<K: ToString, O: Cacheable>(key: K) -> String {
     format!("{}:{}", O::model_name(), key.to_string())
 }
 
+impl CacheBatch for MemoryCache {
+    fn insert_many<K: ToString, O: Cacheable + Clone + 'static>(&self, items: &[(K, O)]) -> Result<()> {
+        {
+            let mut cache = self.inner.obj_cache.write();
+            for &(ref key, ref obj) in items {
+                let tkey = gen_key::<String, O>(key.to_string());
+                let exp = obj.expires_after().map(|ttl| Expiration::new(ttl));
+                cache.insert(tkey.clone(), (Box::new(obj.clone()), exp));
+                self.inner.touch(&tkey);
+            }
+        }
+
+        for _ in 0..items.len() {
+            self.inner.evict_lru_if_over_capacity();
+        }
+        Ok(())
+    }
+
+    fn get_many<K: ToString, O: Cacheable + Clone + 'static>(&self, keys: &[K]) -> Result<Vec<Option<O>>> {
+        let cache = self.inner.obj_cache.read();
+        let mut results = Vec::with_capacity(keys.len());
+
+        for key in keys {
+            let tkey = gen_key::<String, O>(key.to_string());
+            match cache.get(&tkey) {
+                Some(&(ref obj, ref exp)) if !exp.as_ref().map_or(false, |e| e.is_expired()) => {
+                    self.inner.touch(&tkey);
+                    results.push(obj.as_any().downcast_ref::<O>().cloned());
+                }
+                _ => results.push(None),
+            }
+        }
+
+        Ok(results)
+    }
+
+    fn remove_many<K: ToString, O: Cacheable>(&self, keys: &[K]) -> Result<()> {
+        let mut cache = self.inner.obj_cache.write();
+        for key in keys {
+            let tkey = gen_key::<String, O>(key.to_string());
+            cache.remove(&tkey);
+            self.inner.forget(&tkey);
+        }
+        Ok(())
+    }
+}
+
 impl CacheFunc for MemoryCache {
+    fn expire(&self, key: &str, ttl: usize) -> Result<bool> {
+        if self.inner.expire_hash(key, ttl) {
+            return Ok(true);
+        }
+
+        Ok(self.inner.expire_set(key, ttl))
+    }
+
     fn hash_delete(&self, key: &str, fields: &[&str]) -> Result<bool> {
         let map = self.inner.hashsets.read();
         if let Some(hash) = map.get(key) {
@@ -1,12 +1,15 @@
 use std::net;
 use std::mem::discriminant;
 use std::collections::hash_map::HashMap;
+use std::str::FromStr;
 use Cache;
 use Cache::Redis;
 use Result;
 use CacheError;
 use Cacheable;
 use CacheAccess;
+use CacheFunc;
+use CacheBatch;
 use redis;
 use redis::Commands;
 use dns_lookup::lookup_host;
@@ -14,6 +17,11 @@ use dns_lookup::lookup_host;
 use r2d2::Pool;
 use r2d2_redis::RedisConnectionManager;
 
+#[cfg(feature = "serde")]
+use cacheable_blob::{CacheableBlob, blob_key_create};
+#[cfg(feature = "serde")]
+use serde_json;
+
 #[allow(dead_code)]
 pub struct RedisCache {
     connection_pool: Pool<RedisConnectionManager>,
@@ -109,6 +117,451 @@ impl CacheAccess for RedisCache {
     }
 }
 
+#[cfg(feature = "serde")]
+impl RedisCache {
+    /// Stores `obj` JSON-encoded under a single key via SET, for types that
+    /// opt into blob storage instead of `Cacheable`'s hash-field layout.
+    pub fn insert_blob<K: ToString, O: CacheableBlob>(&mut self, key: K, obj: &O) -> Result<()> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        let redis_key = blob_key_create::<K, O>(key);
+        let json = match serde_json::to_string(obj) {
+            Ok(j) => j,
+            Err(e) => return Err(CacheError::InsertionError(e.to_string())),
+        };
+
+        if let Some(ttl) = obj.expires_after() {
+            redis_set_with_expire(&connection, redis_key, json, ttl)
+        } else {
+            redis_set(&connection, redis_key, json)
+        }
+    }
+
+    pub fn get_blob<K: ToString, O: CacheableBlob>(&mut self, key: K) -> Result<Option<O>> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match redis_get(&connection, blob_key_create::<K, O>(key)) {
+            Ok(Some(json)) => Ok(serde_json::from_str(json.as_str()).ok()),
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn remove_blob<K: ToString, O: CacheableBlob>(&mut self, key: K) -> Result<()> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        redis_delete(&connection, blob_key_create::<K, O>(key))
+    }
+}
+
+#[cfg(feature = "serde")]
+fn redis_set(con: &redis::Connection, key: String, value: String) -> Result<()> {
+    match con.set::<String, String, ()>(key, value) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(CacheError::InsertionError(e.to_string())),
+    }
+}
+
+#[cfg(feature = "serde")]
+fn redis_set_with_expire(con: &redis::Connection, key: String, value: String, ttl_sec: usize) -> Result<()> {
+    match con.set_ex::<String, String, ()>(key, value, ttl_sec) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(CacheError::InsertionError(e.to_string())),
+    }
+}
+
+#[cfg(feature = "serde")]
+fn redis_get(con: &redis::Connection, key: String) -> Result<Option<String>> {
+    match con.get::<String, Option<String>>(key) {
+        Ok(v) => Ok(v),
+        Err(e) => Err(CacheError::Other(e.to_string())),
+    }
+}
+
+impl CacheFunc for RedisCache {
+    fn expire(&self, key: &str, ttl: usize) -> Result<bool> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.expire::<&str, bool>(key, ttl) {
+            Ok(was_set) => Ok(was_set),
+            Err(e) => Err(CacheError::Other(e.to_string())),
+        }
+    }
+
+    fn hash_delete(&self, key: &str, fields: &[&str]) -> Result<bool> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.hdel::<&str, &[&str], u64>(key, fields) {
+            Ok(_) => Ok(true),
+            Err(e) => Err(CacheError::DeletionError(e.to_string())),
+        }
+    }
+
+    fn hash_exists(&self, key: &str, field: &str) -> Result<bool> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.hexists::<&str, &str, bool>(key, field) {
+            Ok(exists) => Ok(exists),
+            Err(e) => Err(CacheError::Other(e.to_string())),
+        }
+    }
+
+    fn hash_get<T: FromStr>(&self, key: &str, field: &str) -> Result<Option<T>> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.hget::<&str, &str, Option<String>>(key, field) {
+            Ok(Some(val)) => T::from_str(val.as_str()).map(Some)
+                .map_err(|_| CacheError::Other("Unable to parse value into desired type".to_string())),
+            Ok(None) => Ok(None),
+            Err(e) => Err(CacheError::Other(e.to_string())),
+        }
+    }
+
+    fn hash_get_all<T: Cacheable + Clone + 'static>(&self, key: &str) -> Result<Option<T>> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        let redis_key = redis_key_create::<&str, T>(key);
+        if let Ok(val) = redis_hash_get_all(&connection, redis_key) {
+            if let Ok(c) = T::from_redis_obj(val) {
+                Ok(Some(c))
+            } else {
+                Ok(None)
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn hash_keys(&self, key: &str) -> Result<Vec<String>> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.hkeys::<&str, Vec<String>>(key) {
+            Ok(keys) => Ok(keys),
+            Err(e) => Err(CacheError::Other(e.to_string())),
+        }
+    }
+
+    fn hash_len(&self, key: &str) -> Result<usize> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.hlen::<&str, usize>(key) {
+            Ok(len) => Ok(len),
+            Err(e) => Err(CacheError::Other(e.to_string())),
+        }
+    }
+
+    fn hash_multiple_get(&self, key: &str, fields: &[&str]) -> Result<Vec<Option<String>>> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match redis::cmd("HMGET").arg(key).arg(fields).query::<Vec<Option<String>>>(&*connection) {
+            Ok(vals) => Ok(vals),
+            Err(e) => Err(CacheError::Other(e.to_string())),
+        }
+    }
+
+    fn hash_multiple_set<V: ToString>(&self, key: &str, fv_pairs: &[(&str, V)]) -> Result<bool> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        let data: Vec<(&str, String)> = fv_pairs.iter().map(|&(f, ref v)| (f, v.to_string())).collect();
+        match connection.hset_multiple::<&str, &str, String, ()>(key, &data) {
+            Ok(_) => Ok(true),
+            Err(e) => Err(CacheError::InsertionError(e.to_string())),
+        }
+    }
+
+    fn hash_set<V: ToString>(&self, key: &str, field: &str, value: V) -> Result<bool> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.hset::<&str, &str, String, ()>(key, field, value.to_string()) {
+            Ok(_) => Ok(true),
+            Err(e) => Err(CacheError::InsertionError(e.to_string())),
+        }
+    }
+
+    fn hash_set_all<T: Cacheable + Clone + 'static>(&self, key: &str, cacheable: T) -> Result<bool> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        let redis_key = redis_key_create::<&str, T>(key);
+        let data = cacheable.to_redis_obj();
+        if let Some(ttl) = cacheable.expires_after() {
+            redis_hash_set_multiple_with_expire(&connection, redis_key, &data, ttl).map(|_| true)
+        } else {
+            redis_hash_set_multiple(&connection, redis_key, &data).map(|_| true)
+        }
+    }
+
+    fn hash_set_if_not_exists<V: ToString>(&self, key: &str, field: &str, value: V) -> Result<bool> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.hset_nx::<&str, &str, String, bool>(key, field, value.to_string()) {
+            Ok(set) => Ok(set),
+            Err(e) => Err(CacheError::InsertionError(e.to_string())),
+        }
+    }
+
+    fn hash_values(&self, key: &str) -> Result<Vec<String>> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.hvals::<&str, Vec<String>>(key) {
+            Ok(vals) => Ok(vals),
+            Err(e) => Err(CacheError::Other(e.to_string())),
+        }
+    }
+
+    fn set_add<V: ToString>(&self, key: &str, members: &[V]) -> Result<bool> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        let data: Vec<String> = members.iter().map(|m| m.to_string()).collect();
+        match connection.sadd::<&str, &Vec<String>, u64>(key, &data) {
+            Ok(_) => Ok(true),
+            Err(e) => Err(CacheError::InsertionError(e.to_string())),
+        }
+    }
+
+    fn set_card(&self, key: &str) -> Result<u64> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.scard::<&str, u64>(key) {
+            Ok(card) => Ok(card),
+            Err(e) => Err(CacheError::Other(e.to_string())),
+        }
+    }
+
+    fn set_diff(&self, keys: &[&str]) -> Result<Vec<String>> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.sdiff::<&[&str], Vec<String>>(keys) {
+            Ok(res) => Ok(res),
+            Err(e) => Err(CacheError::Other(e.to_string())),
+        }
+    }
+
+    fn set_diffstore(&self, diff_name: &str, keys: &[&str]) -> Result<u64> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.sdiffstore::<&str, &[&str], u64>(diff_name, keys) {
+            Ok(n) => Ok(n),
+            Err(e) => Err(CacheError::InsertionError(e.to_string())),
+        }
+    }
+
+    fn set_inter(&self, keys: &[&str]) -> Result<Vec<String>> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.sinter::<&[&str], Vec<String>>(keys) {
+            Ok(res) => Ok(res),
+            Err(e) => Err(CacheError::Other(e.to_string())),
+        }
+    }
+
+    fn set_interstore(&self, inter_name: &str, keys: &[&str]) -> Result<u64> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.sinterstore::<&str, &[&str], u64>(inter_name, keys) {
+            Ok(n) => Ok(n),
+            Err(e) => Err(CacheError::InsertionError(e.to_string())),
+        }
+    }
+
+    fn set_ismember<V: ToString>(&self, key: &str, member: V) -> Result<bool> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.sismember::<&str, String, bool>(key, member.to_string()) {
+            Ok(is_member) => Ok(is_member),
+            Err(e) => Err(CacheError::Other(e.to_string())),
+        }
+    }
+
+    fn set_members(&self, key: &str) -> Result<Vec<String>> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.smembers::<&str, Vec<String>>(key) {
+            Ok(members) => Ok(members),
+            Err(e) => Err(CacheError::Other(e.to_string())),
+        }
+    }
+
+    fn set_move<V: ToString>(&self, key1: &str, key2: &str, member: V) -> Result<bool> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.smove::<&str, &str, String, bool>(key1, key2, member.to_string()) {
+            Ok(moved) => Ok(moved),
+            Err(e) => Err(CacheError::Other(e.to_string())),
+        }
+    }
+
+    fn set_rem<V: ToString>(&self, key: &str, member: V) -> Result<bool> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.srem::<&str, String, u64>(key, member.to_string()) {
+            Ok(n) => Ok(n > 0),
+            Err(e) => Err(CacheError::DeletionError(e.to_string())),
+        }
+    }
+
+    fn set_union(&self, keys: &[&str]) -> Result<Vec<String>> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.sunion::<&[&str], Vec<String>>(keys) {
+            Ok(res) => Ok(res),
+            Err(e) => Err(CacheError::Other(e.to_string())),
+        }
+    }
+
+    fn set_unionstore(&self, union_name: &str, keys: &[&str]) -> Result<u64> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        match connection.sunionstore::<&str, &[&str], u64>(union_name, keys) {
+            Ok(n) => Ok(n),
+            Err(e) => Err(CacheError::InsertionError(e.to_string())),
+        }
+    }
+}
+
+impl CacheBatch for RedisCache {
+    fn insert_many<K: ToString, O: Cacheable + Clone + 'static>(&self, items: &[(K, O)]) -> Result<()> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        let mut pipe = redis::pipe();
+        for &(ref key, ref obj) in items {
+            let redis_key = redis_key_create::<String, O>(key.to_string());
+            let data = obj.to_redis_obj();
+            pipe.hset_multiple(redis_key.clone(), &data).ignore();
+            if let Some(ttl) = obj.expires_after() {
+                pipe.expire(redis_key, ttl).ignore();
+            }
+        }
+
+        match pipe.query::<()>(&connection) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(CacheError::InsertionError(e.to_string())),
+        }
+    }
+
+    fn get_many<K: ToString, O: Cacheable + Clone + 'static>(&self, keys: &[K]) -> Result<Vec<Option<O>>> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        let mut pipe = redis::pipe();
+        for key in keys {
+            pipe.hgetall(redis_key_create::<String, O>(key.to_string()));
+        }
+
+        let raw: Vec<HashMap<String, String>> = match pipe.query(&connection) {
+            Ok(v) => v,
+            Err(e) => return Err(CacheError::Other(e.to_string())),
+        };
+
+        Ok(raw.into_iter().map(|val| O::from_redis_obj(val).ok()).collect())
+    }
+
+    fn remove_many<K: ToString, O: Cacheable>(&self, keys: &[K]) -> Result<()> {
+        let connection = match self.connection_pool.get() {
+            Ok(con) => con,
+            Err(e) => return Err(CacheError::ConnectionError(e.to_string())),
+        };
+
+        let mut pipe = redis::pipe();
+        for key in keys {
+            pipe.del(redis_key_create::<String, O>(key.to_string())).ignore();
+        }
+
+        match pipe.query::<()>(&connection) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(CacheError::DeletionError(String::new())),
+        }
+    }
+}
+
 fn redis_key_create<K: ToString, O: Cacheable>(key: K) -> String {
     let mut redis_key = String::from(O::model_name());
     redis_key.push_str(":");